@@ -26,7 +26,7 @@
 
 use argon2::{Algorithm, Argon2, Params, Version};
 use chacha20poly1305::{
-    aead::{Aead, KeyInit},
+    aead::{Aead, KeyInit, Payload},
     Key, XChaCha20Poly1305, XNonce,
 };
 use rand_core::{OsRng, RngCore};
@@ -46,16 +46,48 @@ const SALT_LENGTH: usize = 16;
 const NONCE_LENGTH: usize = 24;
 const KEY_LENGTH: usize = 32;
 
+/// Argon2id cost parameters a vault was (or should be) derived under.
+///
+/// Stored alongside the salt in every container so a stale vault's
+/// parameters can be compared against the crate's current recommendation
+/// without needing to decrypt anything first.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl KdfParams {
+    /// The parameters new vaults are derived under, and the target of any
+    /// transparent upgrade.
+    pub const CURRENT: KdfParams = KdfParams {
+        m_cost: ARGON2_M_COST,
+        t_cost: ARGON2_T_COST,
+        p_cost: ARGON2_P_COST,
+    };
+
+    /// Whether `self` is weaker than `other` (lower memory or time cost)
+    /// and should be upgraded. This is the per-vault policy check that
+    /// keeps an unlock from re-hashing a vault that's already current.
+    pub fn weaker_than(self, other: KdfParams) -> bool {
+        self.m_cost < other.m_cost || self.t_cost < other.t_cost
+    }
+}
+
 /* --------------- Master Key ---------------- */
 
 #[derive(Zeroize, ZeroizeOnDrop)]
 pub struct MasterKey {
     key: Zeroizing<[u8; KEY_LENGTH]>,
     salt: [u8; SALT_LENGTH],
+    #[zeroize(skip)]
+    params: KdfParams,
 }
 
 impl MasterKey {
-    /// Derives a master key from password using Argon2id
+    /// Derives a master key from password using Argon2id, under a fresh
+    /// salt and the crate's current recommended parameters.
     pub fn from_password(password: &str) -> Result<Self, VaultError> {
         let mut salt = [0u8; SALT_LENGTH];
         OsRng.fill_bytes(&mut salt);
@@ -63,16 +95,24 @@ impl MasterKey {
         Self::from_password_with_salt(password, &salt)
     }
 
-    /// Derives a master key from password using provided salt
+    /// Derives a master key from password and salt, under the crate's
+    /// current recommended parameters.
     pub fn from_password_with_salt(password: &str, salt: &[u8]) -> Result<Self, VaultError> {
-        let params = Params::new(
-            ARGON2_M_COST,
-            ARGON2_T_COST,
-            ARGON2_P_COST,
-            Some(KEY_LENGTH),
-        )?;
+        Self::from_password_with_params(password, salt, KdfParams::CURRENT)
+    }
 
-        let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+    /// Derives a master key from password, salt, and explicit KDF
+    /// parameters. Used to unlock a vault stored under parameters other
+    /// than the crate's current defaults, and to re-derive under stronger
+    /// ones during a transparent upgrade.
+    pub fn from_password_with_params(
+        password: &str,
+        salt: &[u8],
+        params: KdfParams,
+    ) -> Result<Self, VaultError> {
+        let argon2_params = Params::new(params.m_cost, params.t_cost, params.p_cost, Some(KEY_LENGTH))?;
+
+        let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, argon2_params);
         let mut key = Zeroizing::new([0u8; KEY_LENGTH]);
 
         argon2.hash_password_into(password.as_bytes(), salt, &mut key)?;
@@ -80,6 +120,7 @@ impl MasterKey {
         Ok(Self {
             key,
             salt: salt.try_into().map_err(|_| VaultError::CryptoError)?,
+            params,
         })
     }
 
@@ -90,82 +131,423 @@ impl MasterKey {
     pub fn salt(&self) -> &[u8; SALT_LENGTH] {
         &self.salt
     }
+
+    pub fn params(&self) -> KdfParams {
+        self.params
+    }
+}
+
+/* --------------- Versioned Header ---------------- */
+
+/// Four-byte file signature identifying a Ferreus vault container.
+const VAULT_MAGIC: [u8; 4] = *b"FVLT";
+
+/// `magic(4) + version(2, big-endian) + kdf(1) + aead(1)`.
+const HEADER_LENGTH: usize = 8;
+
+/// Key-derivation function tagged in the header. Only `Argon2id` exists
+/// today, but the tag lets a future algorithm be introduced without
+/// breaking the ability to read vaults written under this one.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum KdfAlgorithm {
+    Argon2id = 1,
+}
+
+/// AEAD scheme tagged in the header, for the same forward-compatibility
+/// reason as `KdfAlgorithm`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum AeadAlgorithm {
+    XChaCha20Poly1305 = 1,
+}
+
+/// Fixed-size header prefixed to every on-disk vault.
+///
+/// Encoded by hand rather than through `bincode`, so its byte layout stays
+/// stable regardless of future `serde`/`bincode` version changes, and so
+/// it can be fed to the AEAD as associated data without re-serializing it.
+#[derive(Clone, Copy, Debug)]
+struct VaultHeader {
+    version: u16,
+    kdf: KdfAlgorithm,
+    aead: AeadAlgorithm,
+}
+
+impl VaultHeader {
+    fn current() -> Self {
+        Self {
+            version: EncryptedVault::CURRENT_VERSION,
+            kdf: KdfAlgorithm::Argon2id,
+            aead: AeadAlgorithm::XChaCha20Poly1305,
+        }
+    }
+
+    fn to_bytes(self) -> [u8; HEADER_LENGTH] {
+        let mut out = [0u8; HEADER_LENGTH];
+        out[0..4].copy_from_slice(&VAULT_MAGIC);
+        out[4..6].copy_from_slice(&self.version.to_be_bytes());
+        out[6] = self.kdf as u8;
+        out[7] = self.aead as u8;
+        out
+    }
+
+    /// Parses and validates the header, rejecting an unrecognized magic as
+    /// corruption and a too-new version as `UnsupportedVersion` rather than
+    /// `CorruptedVault` — the file is intact, just from a newer build.
+    fn from_bytes(bytes: &[u8]) -> Result<Self, VaultError> {
+        if bytes.len() < HEADER_LENGTH || bytes[0..4] != VAULT_MAGIC {
+            return Err(VaultError::CorruptedVault);
+        }
+
+        let version = u16::from_be_bytes([bytes[4], bytes[5]]);
+        if version > EncryptedVault::CURRENT_VERSION {
+            return Err(VaultError::UnsupportedVersion);
+        }
+
+        let kdf = match bytes[6] {
+            1 => KdfAlgorithm::Argon2id,
+            _ => return Err(VaultError::CorruptedVault),
+        };
+
+        let aead = match bytes[7] {
+            1 => AeadAlgorithm::XChaCha20Poly1305,
+            _ => return Err(VaultError::CorruptedVault),
+        };
+
+        Ok(Self { version, kdf, aead })
+    }
 }
 
 /* --------------- Encrypted Vault Container ---------------- */
 
+/// The part of the container that's actually encoded through `bincode`;
+/// the header lives outside this and is authenticated as AEAD associated
+/// data instead of being serialized alongside it.
 #[derive(Serialize, Deserialize)]
+struct EncryptedBody {
+    salt: [u8; SALT_LENGTH],
+    kdf_params: KdfParams,
+    nonce: [u8; NONCE_LENGTH],
+    ciphertext: Vec<u8>,
+}
+
 pub struct EncryptedVault {
-    pub version: u32,
-    pub salt: [u8; SALT_LENGTH],
-    pub nonce: [u8; NONCE_LENGTH],
-    pub ciphertext: Vec<u8>,
+    header: VaultHeader,
+    body: EncryptedBody,
 }
 
 impl EncryptedVault {
-    pub const CURRENT_VERSION: u32 = 1;
+    pub const CURRENT_VERSION: u16 = 1;
 
-    /// Encrypt serialized vault data
+    /// Encrypt serialized vault data.
+    ///
+    /// The header is passed to the AEAD as associated data, so tampering
+    /// with the declared version or algorithm tags fails authentication
+    /// exactly like tampering with the ciphertext does.
     pub fn encrypt(plaintext: &[u8], master_key: &MasterKey) -> Result<Self, VaultError> {
         let mut nonce = [0u8; NONCE_LENGTH];
         OsRng.fill_bytes(&mut nonce);
 
+        let header = VaultHeader::current();
         let cipher = XChaCha20Poly1305::new(Key::from_slice(master_key.key_bytes()));
-
-        let ciphertext = cipher.encrypt(XNonce::from_slice(&nonce), plaintext)?;
+        let ciphertext = cipher.encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad: &header.to_bytes(),
+            },
+        )?;
 
         Ok(Self {
-            version: Self::CURRENT_VERSION,
-            salt: *master_key.salt(),
-            nonce,
-            ciphertext,
+            header,
+            body: EncryptedBody {
+                salt: *master_key.salt(),
+                kdf_params: master_key.params(),
+                nonce,
+                ciphertext,
+            },
         })
     }
 
-    /// Decrypt vault payload
+    /// Decrypt vault payload.
     ///
-    /// Authentication failure is treated generically
-
+    /// Authentication failure (including a tampered header) is treated
+    /// generically.
     pub fn decrypt(&self, master_key: &MasterKey) -> Result<SecureBytes, VaultError> {
         let cipher = XChaCha20Poly1305::new(Key::from_slice(master_key.key_bytes()));
 
-        let plaintext =
-            cipher.decrypt(XNonce::from_slice(&self.nonce), self.ciphertext.as_ref())?;
+        let plaintext = cipher.decrypt(
+            XNonce::from_slice(&self.body.nonce),
+            Payload {
+                msg: self.body.ciphertext.as_ref(),
+                aad: &self.header.to_bytes(),
+            },
+        )?;
+
+        Ok(Zeroizing::new(plaintext))
+    }
 
-        ok(Zeroizing::new(plaintext))
+    /// Format version this container was written at. Used by the storage
+    /// layer to decide whether an in-place migration is needed after the
+    /// vault is successfully decrypted.
+    pub fn version(&self) -> u16 {
+        self.header.version
+    }
+
+    pub fn salt(&self) -> &[u8; SALT_LENGTH] {
+        &self.body.salt
+    }
+
+    /// KDF parameters this container was derived under. Compared against
+    /// `KdfParams::CURRENT` on unlock to decide whether to upgrade.
+    pub fn kdf_params(&self) -> KdfParams {
+        self.body.kdf_params
     }
 
     pub fn to_bytes(&self) -> Result<Vec<u8>, VaultError> {
-        Ok(bincode::serialize(self)?)
+        let mut out = self.header.to_bytes().to_vec();
+        out.extend(bincode::serialize(&self.body)?);
+        Ok(out)
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, VaultError> {
-        Ok(bincode::deserialize(bytes)?)
+        let header = VaultHeader::from_bytes(bytes)?;
+        let body = bincode::deserialize(&bytes[HEADER_LENGTH..])?;
+        Ok(Self { header, body })
     }
 }
 
 /*--------------------- Password Strength Estimator --------------- */
-/// Estimates password entropy strength
-/// Returns score 0-100
-pub fn estimate_password_strength(password: &str) -> f64 {
-    let length = password.len() as f64;
 
-    let mut charset = 0.0;
+/// Compact embedded list of common passwords/dictionary words, ordered
+/// roughly by how often they show up in leaked-password corpora. The
+/// position in the list (1-indexed) is used directly as its guess rank.
+const COMMON_PASSWORDS: &[&str] = &[
+    "password", "123456", "12345678", "qwerty", "letmein", "admin", "welcome", "monkey",
+    "dragon", "master", "login", "princess", "sunshine", "iloveyou", "football", "baseball",
+    "superman", "trustno1", "hello", "freedom", "whatever", "starwars", "shadow", "michael",
+    "jennifer", "jordan", "hunter", "buster", "soccer", "harley", "ranger", "daniel", "computer",
+    "michelle", "jessica", "pepper", "abc123", "123123", "121212", "flower", "hottie", "loveme",
+    "summer", "andrea", "passw0rd", "taylor", "angel", "cheese", "tigger", "dakota", "ginger",
+    "amanda", "nicole", "chelsea", "biteme", "matthew", "access", "yankees", "dallas", "austin",
+    "thunder", "taylor1", "matrix", "mobilemail", "coffee", "bulldog", "martin", "merlin",
+];
+
+/// Fixed-row keyboard adjacency runs. Detected the same way as dictionary
+/// substrings, but against a small hand-picked table instead of a ranked list.
+const KEYBOARD_RUNS: &[&str] = &[
+    "qwerty", "qwertyuiop", "asdfghjkl", "zxcvbnm", "asdf", "zxcv", "qazwsx", "wasd", "1qaz",
+    "yuiop", "hjkl",
+];
+
+/// Characters treated as look-alike substitutions for a dictionary letter
+/// (`@` -> `a`, `0` -> `o`, ...), applied before dictionary/keyboard matching.
+fn leet_normalize(c: char) -> char {
+    match c {
+        '@' => 'a',
+        '0' => 'o',
+        '3' => 'e',
+        '1' | '!' => 'i',
+        '$' | '5' => 's',
+        '7' => 't',
+        other => other,
+    }
+}
+
+/// A single recognized pattern spanning `[start, end)` in the normalized
+/// password, and the estimated number of guesses needed to produce it.
+struct Match {
+    start: usize,
+    end: usize,
+    guesses: f64,
+}
+
+fn dictionary_matches(normalized: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+
+    for start in 0..normalized.len() {
+        for end in (start + 1)..=normalized.len() {
+            if end - start < 3 {
+                continue;
+            }
+
+            let candidate: String = normalized[start..end].iter().collect();
+
+            if let Some(rank) = COMMON_PASSWORDS.iter().position(|word| *word == candidate) {
+                matches.push(Match {
+                    start,
+                    end,
+                    guesses: (rank + 1) as f64,
+                });
+            }
+        }
+    }
+
+    matches
+}
+
+fn keyboard_matches(normalized: &[char]) -> Vec<Match> {
+    let joined: String = normalized.iter().collect();
+    let mut matches = Vec::new();
+
+    for run in KEYBOARD_RUNS {
+        let mut offset = 0;
+        while let Some(pos) = joined[offset..].find(run) {
+            let start = offset + pos;
+            let end = start + run.chars().count();
+            matches.push(Match {
+                start,
+                end,
+                guesses: 10.0 * run.len() as f64,
+            });
+            offset = start + 1;
+        }
+    }
+
+    matches
+}
+
+/// Runs of 3+ repeated characters, e.g. `aaa`.
+fn repeat_matches(normalized: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    let mut i = 0;
+
+    while i < normalized.len() {
+        let mut j = i + 1;
+        while j < normalized.len() && normalized[j] == normalized[i] {
+            j += 1;
+        }
+
+        if j - i >= 3 {
+            matches.push(Match {
+                start: i,
+                end: j,
+                guesses: 10.0 * (j - i) as f64,
+            });
+        }
+
+        i = j;
+    }
+
+    matches
+}
+
+/// Runs of 3+ ascending or descending characters, e.g. `abc`, `321`.
+fn sequence_matches(normalized: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    let mut i = 0;
+
+    while i + 1 < normalized.len() {
+        let step = normalized[i + 1] as i32 - normalized[i] as i32;
+
+        if step == 1 || step == -1 {
+            let mut j = i + 1;
+            while j + 1 < normalized.len() && (normalized[j + 1] as i32 - normalized[j] as i32) == step {
+                j += 1;
+            }
+
+            if j - i + 1 >= 3 {
+                matches.push(Match {
+                    start: i,
+                    end: j + 1,
+                    guesses: 10.0 * (j - i + 1) as f64,
+                });
+            }
+
+            i = j;
+        } else {
+            i += 1;
+        }
+    }
+
+    matches
+}
 
-    if password.chars().any(|c| c.is_lowercase()) {
-        charset += 26.0;
+/// Guess-space size of the character class a single character belongs to,
+/// used as the brute-force fallback cost for positions no match covers.
+fn charset_size_of(c: char) -> f64 {
+    if c.is_ascii_lowercase() || c.is_ascii_uppercase() {
+        26.0
+    } else if c.is_ascii_digit() {
+        10.0
+    } else {
+        33.0
     }
-    if password.chars().any(|c| c.is_uppercase()) {
-        charset += 26.0;
+}
+
+fn factorial(n: u32) -> f64 {
+    (1..=n).fold(1.0, |acc, x| acc * x as f64)
+}
+
+/// Estimates the number of guesses needed to find `password` via a
+/// zxcvbn-style pattern match: scan for dictionary, repeat, sequence, and
+/// keyboard-adjacency matches, then run a dynamic program over prefix
+/// lengths to find the cheapest way to cover the whole string.
+fn estimate_guesses(password: &str) -> f64 {
+    let lowered: Vec<char> = password.to_lowercase().chars().collect();
+    let normalized: Vec<char> = lowered.iter().copied().map(leet_normalize).collect();
+    let len = normalized.len();
+
+    if len == 0 {
+        return 1.0;
     }
-    if password.chars().any(|c| c.is_ascii_digit()) {
-        charset += 10.0;
+
+    let mut all_matches = Vec::new();
+    all_matches.extend(dictionary_matches(&normalized));
+    all_matches.extend(keyboard_matches(&normalized));
+    all_matches.extend(repeat_matches(&lowered));
+    all_matches.extend(sequence_matches(&lowered));
+
+    let mut matches_ending_at: Vec<Vec<&Match>> = vec![Vec::new(); len + 1];
+    for m in &all_matches {
+        if m.end <= len {
+            matches_ending_at[m.end].push(m);
+        }
     }
-    if password.chars().any(|c| c.is_alphanumeric()) {
-        charset += 33.0;
+
+    // dp[k] = (minimum guesses to cover the first k characters, number of
+    // *genuine* pattern matches used to get there — brute-forced single
+    // characters don't count, since they have no ordering to permute)
+    let mut dp: Vec<(f64, u32)> = Vec::with_capacity(len + 1);
+    dp.push((1.0, 0));
+
+    for k in 1..=len {
+        // Brute-force fallback: treat position k-1 as an uncovered single character.
+        let (prev_guesses, prev_count) = dp[k - 1];
+        let mut best = (prev_guesses * charset_size_of(lowered[k - 1]), prev_count);
+
+        for m in &matches_ending_at[k] {
+            let (start_guesses, start_count) = dp[m.start];
+            let candidate = (start_guesses * m.guesses, start_count + 1);
+
+            if candidate.0 < best.0 {
+                best = candidate;
+            }
+        }
+
+        dp.push(best);
+    }
+
+    let (total_guesses, match_count) = dp[len];
+
+    // Combinatorial ordering factor: the *matched patterns* used to cover
+    // the password could have been tried in any order.
+    total_guesses * factorial(match_count.min(12))
+}
+
+/// Estimates password strength on a 0-100 scale using a pattern-aware
+/// guess model (dictionary words, leet substitutions, repeats, sequences,
+/// and keyboard runs), rather than a flat per-character entropy count.
+///
+/// This deliberately scores predictable-but-"complex-looking" passwords
+/// (e.g. `Password123!`) low, since they collapse to a small number of
+/// guesses once the dictionary word and the trailing sequence are spotted.
+pub fn estimate_password_strength(password: &str) -> f64 {
+    if password.is_empty() {
+        return 0.0;
     }
 
-    let entropy = length * charset.log2();
+    let guesses = estimate_guesses(password).max(1.0);
+    let bits = guesses.log2();
 
-    (entropy / 128.0 * 100.0).clamp(0.0, 100.0)
+    (bits / 128.0 * 100.0).clamp(0.0, 100.0)
 }
@@ -69,6 +69,21 @@ pub enum VaultError {
     /// Operation attempted while the vault is locked.
     #[error("Vault is locked")]
     VaultLocked,
+
+    /// Import/export requested an interchange format tag that isn't recognized.
+    #[error("Unsupported import/export format")]
+    UnsupportedFormat,
+
+    /// Vault header declares a format version newer than this build
+    /// understands. Distinct from `CorruptedVault` so users get an
+    /// actionable "upgrade Ferreus Vault" message instead of "it's broken".
+    #[error("Vault format version is newer than this build supports")]
+    UnsupportedVersion,
+
+    /// A plaintext export/import format was requested without the caller
+    /// passing the explicit confirmation flag.
+    #[error("Plaintext export/import requires explicit confirmation")]
+    ExportNotConfirmed,
 }
 
 //
@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: GPL-3.0-only
+// Copyright (C) 2026 Ferreus Vault Contributors
+//
+// This file is part of Ferreus Vault.
+//
+// Ferreus Vault is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License version 3 only,
+// as published by the Free Software Foundation.
+//
+// Ferreus Vault is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Configurable, cryptographically secure password/passphrase generation
+//!
+//! Responsibilities:
+//! - Generate random passwords honoring a caller-chosen character-class policy
+//! - Generate diceware-style passphrases from an embedded word list
+//!
+//! Security goals:
+//! - All randomness comes from the OS CSPRNG (`OsRng`), matching `crypto.rs`
+//! - Generated secrets are returned in `SecureString` so they are zeroized on drop
+
+use rand::{rngs::OsRng, Rng};
+
+use crate::crypto::estimate_password_strength;
+use crate::memory::SecureString;
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &[u8] = b"0123456789";
+const SYMBOLS: &[u8] = b"!@#$%^&*()-_=+[]{};:,.<>?";
+
+/// Compact embedded word list for `generate_passphrase`. Also reused by
+/// `memory::generate_secure_passphrase` so the crate only carries one copy.
+pub(crate) const WORDLIST: &[&str] = &[
+    "amber", "anchor", "anvil", "apple", "arrow", "ash", "aspen", "badge", "banjo", "barrel",
+    "basil", "beacon", "beaver", "birch", "bison", "blanket", "bloom", "bolt", "bramble", "brook",
+    "cactus", "candle", "canyon", "cedar", "cinder", "clover", "cobalt", "comet", "copper", "coral",
+    "cradle", "crane", "crater", "crescent", "cricket", "current", "dahlia", "daisy", "delta",
+    "desert", "dove", "dune", "ember", "falcon", "feather", "fern", "fjord", "flint", "forest",
+    "fossil", "garnet", "glacier", "granite", "harbor", "hazel", "heron", "hickory", "hollow",
+    "indigo", "ivory", "jasper", "juniper", "kestrel", "lagoon", "lantern", "larch", "lichen",
+    "linen", "lotus", "lumen", "magpie", "maple", "marble", "meadow", "mesa", "mirror", "mist",
+    "moss", "nectar", "nimbus", "oasis", "obsidian", "onyx", "opal", "orchard", "otter", "paddle",
+    "palm", "pebble", "pecan", "pepper", "petal", "pine", "plume", "poplar", "prairie", "quartz",
+    "quill", "raven", "reed", "ridge", "river", "rowan", "saffron", "sage", "salmon", "sandbar",
+    "sequoia", "shale", "shore", "sienna", "silver", "sorrel", "sparrow", "spruce", "storm",
+    "sunrise", "swift", "sycamore", "tarn", "thicket", "thistle", "thunder", "timber", "topaz",
+    "trellis", "tundra", "umbra", "valley", "velvet", "violet", "walnut", "warbler", "willow",
+    "wisteria", "zephyr",
+];
+
+/// Character-class policy for `generate_password`.
+#[derive(Debug, Clone, Copy)]
+pub struct PasswordOptions {
+    pub length: usize,
+    pub lowercase: bool,
+    pub uppercase: bool,
+    pub digits: bool,
+    pub symbols: bool,
+}
+
+impl Default for PasswordOptions {
+    fn default() -> Self {
+        Self {
+            length: 16,
+            lowercase: true,
+            uppercase: true,
+            digits: true,
+            symbols: true,
+        }
+    }
+}
+
+/// Generates a random password satisfying `opts`.
+///
+/// At least one character from each enabled class is guaranteed to
+/// appear; the remaining positions are filled from the union of enabled
+/// classes and the whole string is shuffled, so the guaranteed
+/// characters don't end up predictably at the front.
+pub fn generate_password(opts: &PasswordOptions) -> SecureString {
+    let mut rng = OsRng;
+
+    let mut classes: Vec<&[u8]> = Vec::new();
+    if opts.lowercase {
+        classes.push(LOWERCASE);
+    }
+    if opts.uppercase {
+        classes.push(UPPERCASE);
+    }
+    if opts.digits {
+        classes.push(DIGITS);
+    }
+    if opts.symbols {
+        classes.push(SYMBOLS);
+    }
+    if classes.is_empty() {
+        classes.push(LOWERCASE);
+    }
+
+    let length = opts.length.max(classes.len());
+
+    let mut chars: Vec<u8> = classes
+        .iter()
+        .map(|class| class[rng.gen_range(0..class.len())])
+        .collect();
+
+    let pool: Vec<u8> = classes.iter().flat_map(|class| class.iter().copied()).collect();
+
+    while chars.len() < length {
+        chars.push(pool[rng.gen_range(0..pool.len())]);
+    }
+
+    for i in (1..chars.len()).rev() {
+        let j = rng.gen_range(0..=i);
+        chars.swap(i, j);
+    }
+
+    SecureString::new(String::from_utf8(chars).expect("generated password is ASCII"))
+}
+
+/// Generates a password satisfying `opts`, regenerating until its
+/// `estimate_password_strength` score reaches `min_score` or
+/// `max_attempts` candidates have been tried (whichever comes first).
+pub fn generate_password_above(opts: &PasswordOptions, min_score: f64, max_attempts: usize) -> SecureString {
+    let mut candidate = generate_password(opts);
+
+    for _ in 1..max_attempts {
+        if estimate_password_strength(&candidate) >= min_score {
+            break;
+        }
+        candidate = generate_password(opts);
+    }
+
+    candidate
+}
+
+/// Generates a passphrase of `word_count` words drawn uniformly from an
+/// embedded word list and joined by `separator`.
+pub fn generate_passphrase(word_count: usize, separator: &str) -> SecureString {
+    let mut rng = OsRng;
+
+    let words: Vec<&str> = (0..word_count)
+        .map(|_| WORDLIST[rng.gen_range(0..WORDLIST.len())])
+        .collect();
+
+    SecureString::new(words.join(separator))
+}
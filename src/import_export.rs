@@ -0,0 +1,334 @@
+// SPDX-License-Identifier: GPL-3.0-only
+// Copyright (C) 2026 Ferreus Vault Contributors
+//
+// This file is part of Ferreus Vault.
+//
+// Ferreus Vault is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License version 3 only,
+// as published by the Free Software Foundation.
+//
+// Ferreus Vault is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Import/export subsystem for interchange with other password managers
+//!
+//! Responsibilities:
+//! - Serialize vault entries into interchange formats (Ferreus JSON,
+//!   Bitwarden JSON, plain CSV)
+//! - Parse foreign interchange formats back into `PasswordEntry` records
+//!
+//! Security goals:
+//! - Route plaintext through `Zeroizing` buffers before it touches disk
+//! - Skip malformed rows on import rather than aborting the whole batch
+//! - Plaintext-revealing formats require the caller to explicitly confirm
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::errors::VaultError;
+use crate::vault::PasswordEntry;
+
+/// Interchange format for vault import/export.
+///
+/// `Ferreus`/`CsvPlain` superseded an earlier `Native`/`Csv` naming; the
+/// old tags still parse (see `Format::parse`) so callers built against
+/// either naming keep working.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Ferreus' own full-fidelity JSON record layout.
+    Ferreus,
+    /// Bitwarden-compatible `items` JSON export/import schema.
+    BitwardenJson,
+    /// Plain CSV with `account_name,username,password,notes` columns.
+    CsvPlain,
+}
+
+impl Format {
+    /// Parses a format tag (as might come from a CLI flag or config file).
+    /// Accepts both the current tags and the `native`/`csv` tags from
+    /// before `Ferreus`/`CsvPlain` were introduced. Unknown tags return
+    /// `VaultError::UnsupportedFormat` rather than being coerced into
+    /// some default format.
+    pub fn parse(tag: &str) -> Result<Self, VaultError> {
+        match tag.to_ascii_lowercase().as_str() {
+            "ferreus" | "native" => Ok(Format::Ferreus),
+            "bitwarden" | "bitwarden_json" | "bitwardenjson" => Ok(Format::BitwardenJson),
+            "csv" | "csv_plain" | "csvplain" => Ok(Format::CsvPlain),
+            _ => Err(VaultError::UnsupportedFormat),
+        }
+    }
+
+    /// Whether this format requires the caller's explicit confirmation
+    /// before writing/reading, because it always carries plaintext secrets.
+    fn requires_confirmation(self) -> bool {
+        matches!(self, Format::BitwardenJson | Format::CsvPlain)
+    }
+}
+
+/* ------------------- Ferreus schema ----------------------- */
+
+#[derive(Serialize, Deserialize)]
+struct FerreusRecord {
+    #[serde(default)]
+    account_name: String,
+    #[serde(default)]
+    username: String,
+    #[serde(default)]
+    password: Option<String>,
+    #[serde(default)]
+    notes: String,
+}
+
+/* ------------------- Bitwarden schema ----------------------- */
+
+#[derive(Serialize, Deserialize)]
+struct BitwardenLogin {
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    password: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BitwardenItem {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    login: Option<BitwardenLogin>,
+    #[serde(default)]
+    notes: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BitwardenExport {
+    items: Vec<BitwardenItem>,
+}
+
+/* ------------------- Import/Export entry points ----------------------- */
+
+/// Serializes vault entries to and parses them back from interchange formats.
+pub struct ImportExport;
+
+impl ImportExport {
+    /// Serialize `entries` as `format` and write them to `out_path`.
+    ///
+    /// `confirm_plaintext` must be `true` for formats that always carry
+    /// plaintext secrets (`BitwardenJson`, `CsvPlain`); callers pass `true`
+    /// only once the user has explicitly asked for a plaintext export.
+    ///
+    /// The rendered plaintext is held in a `Zeroizing` buffer until the
+    /// moment it is written, so a failed write never leaves a stray copy
+    /// lingering in memory longer than necessary.
+    pub fn export(
+        entries: &[PasswordEntry],
+        format: Format,
+        out_path: impl AsRef<Path>,
+        confirm_plaintext: bool,
+    ) -> Result<(), VaultError> {
+        if format.requires_confirmation() && !confirm_plaintext {
+            return Err(VaultError::ExportNotConfirmed);
+        }
+
+        let rendered = match format {
+            Format::Ferreus => Zeroizing::new(Self::render_ferreus(entries)?),
+            Format::BitwardenJson => Zeroizing::new(Self::render_bitwarden(entries)?),
+            Format::CsvPlain => Zeroizing::new(Self::render_csv(entries)),
+        };
+
+        fs::write(out_path, rendered.as_bytes()).map_err(VaultError::IoError)
+    }
+
+    /// Parse `in_path` as `format` and return the entries it contains.
+    ///
+    /// Individual malformed rows are skipped rather than aborting the
+    /// whole batch, so a partially-corrupt export still yields what it can.
+    pub fn import(
+        format: Format,
+        in_path: impl AsRef<Path>,
+        confirm_plaintext: bool,
+    ) -> Result<Vec<PasswordEntry>, VaultError> {
+        if format.requires_confirmation() && !confirm_plaintext {
+            return Err(VaultError::ExportNotConfirmed);
+        }
+
+        let contents = Zeroizing::new(fs::read_to_string(in_path).map_err(VaultError::IoError)?);
+
+        match format {
+            Format::Ferreus => Self::parse_ferreus(&contents),
+            Format::BitwardenJson => Self::parse_bitwarden(&contents),
+            Format::CsvPlain => Ok(Self::parse_csv(&contents)),
+        }
+    }
+
+    fn render_ferreus(entries: &[PasswordEntry]) -> Result<String, VaultError> {
+        let records: Vec<FerreusRecord> = entries
+            .iter()
+            .map(|entry| FerreusRecord {
+                account_name: entry.account_name.clone(),
+                username: entry.username.clone(),
+                password: Some(entry.password.clone()),
+                notes: entry.notes.clone(),
+            })
+            .collect();
+
+        serde_json::to_string_pretty(&records).map_err(|_| VaultError::SerializationError)
+    }
+
+    fn parse_ferreus(contents: &str) -> Result<Vec<PasswordEntry>, VaultError> {
+        let records: Vec<serde_json::Value> =
+            serde_json::from_str(contents).map_err(|_| VaultError::SerializationError)?;
+
+        Ok(records
+            .into_iter()
+            .filter_map(|value| {
+                let record: FerreusRecord = serde_json::from_value(value).ok()?;
+
+                Some(PasswordEntry::new(
+                    record.account_name,
+                    record.username,
+                    record.password?,
+                    record.notes,
+                ))
+            })
+            .collect())
+    }
+
+    fn render_bitwarden(entries: &[PasswordEntry]) -> Result<String, VaultError> {
+        let items = entries
+            .iter()
+            .map(|entry| BitwardenItem {
+                name: entry.account_name.clone(),
+                login: Some(BitwardenLogin {
+                    username: Some(entry.username.clone()),
+                    password: Some(entry.password.clone()),
+                }),
+                notes: Some(entry.notes.clone()),
+            })
+            .collect();
+
+        serde_json::to_string_pretty(&BitwardenExport { items })
+            .map_err(|_| VaultError::SerializationError)
+    }
+
+    fn parse_bitwarden(contents: &str) -> Result<Vec<PasswordEntry>, VaultError> {
+        let mut export: serde_json::Value =
+            serde_json::from_str(contents).map_err(|_| VaultError::SerializationError)?;
+
+        let items = match export.get_mut("items").map(serde_json::Value::take) {
+            Some(serde_json::Value::Array(items)) => items,
+            _ => return Err(VaultError::SerializationError),
+        };
+
+        Ok(items
+            .into_iter()
+            .filter_map(|value| {
+                let item: BitwardenItem = serde_json::from_value(value).ok()?;
+                let login = item.login.unwrap_or(BitwardenLogin {
+                    username: None,
+                    password: None,
+                });
+
+                Some(PasswordEntry::new(
+                    item.name,
+                    login.username.unwrap_or_default(),
+                    login.password?,
+                    item.notes.unwrap_or_default(),
+                ))
+            })
+            .collect())
+    }
+
+    fn render_csv(entries: &[PasswordEntry]) -> String {
+        let mut out = String::from("account_name,username,password,notes\n");
+
+        for entry in entries {
+            out.push_str(&format!(
+                "{},{},{},{}\n",
+                csv_escape(&entry.account_name),
+                csv_escape(&entry.username),
+                csv_escape(&entry.password),
+                csv_escape(&entry.notes),
+            ));
+        }
+
+        out
+    }
+
+    fn parse_csv(contents: &str) -> Vec<PasswordEntry> {
+        parse_csv_rows(contents)
+            .into_iter()
+            .skip(1)
+            .filter_map(|fields| {
+                if fields.len() < 4 {
+                    return None;
+                }
+
+                Some(PasswordEntry::new(
+                    fields[0].clone(),
+                    fields[1].clone(),
+                    fields[2].clone(),
+                    fields[3].clone(),
+                ))
+            })
+            .collect()
+    }
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Splits RFC-4180 CSV `contents` into rows of unescaped fields, undoing
+/// `csv_escape`: quoted fields may contain commas and newlines, and `""`
+/// inside a quoted field decodes to a single `"`. The counterpart to
+/// `csv_escape` must live here too, or export/import silently desync on
+/// any field containing a comma.
+fn parse_csv_rows(contents: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows
+}
@@ -23,26 +23,41 @@
 
 pub mod crypto;
 pub mod errors;
+pub mod generator;
+pub mod import_export;
 pub mod memory;
 pub mod storage;
 pub mod vault;
 
-use std::cmp::Reverse;
+use std::fs;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use crate::crypto::{estimate_password_strength, MasterKey};
 use crate::errors::VaultError;
-use crate::storage::VaultStorage;
-use crate::vault::VaultData;
+use crate::generator::PasswordOptions;
+use crate::import_export::{Format, ImportExport};
+use crate::memory::{constant_time_compare, SecureString};
+use crate::storage::{LocalFileBackend, VaultBackend};
+use crate::vault::{Plain, Vault};
+
+/// Default number of candidates tried by `VaultManager::generate_password`
+/// before giving up on hitting the requested strength threshold.
+const MAX_GENERATION_ATTEMPTS: usize = 10;
+
+/// Minimum `estimate_password_strength` score a master password must clear,
+/// on top of the character-class rule. Catches passwords like
+/// `Password123!` that satisfy every class but collapse to a handful of
+/// guesses once the dictionary word and trailing sequence are spotted.
+const MIN_MASTER_PASSWORD_STRENGTH: f64 = 50.0;
 
 /* ------------------ Vault Manager -------------------------- */
 
 pub struct VaultManager {
-    vault_data: Arc<Mutex<Option<VaultData>>>,
+    vault_data: Arc<Mutex<Option<Vault<Plain>>>>,
     master_key: Arc<Mutex<Option<MasterKey>>>,
-    storage: VaultStorage,
+    backend: Box<dyn VaultBackend>,
 
     auto_lock_timeout: Duration,
     last_activity: Instant,
@@ -50,10 +65,16 @@ pub struct VaultManager {
 
 impl VaultManager {
     pub fn new(vault_path: impl AsRef<Path>) -> Self {
+        Self::with_backend(Box::new(LocalFileBackend::new(vault_path)))
+    }
+
+    /// Build a manager over any `VaultBackend`, e.g. an in-memory backend
+    /// for tests or a remote object-store backend.
+    pub fn with_backend(backend: Box<dyn VaultBackend>) -> Self {
         Self {
             vault_data: Arc::new(Mutex::new(None)),
             master_key: Arc::new(Mutex::new(None)),
-            storage: VaultStorage::new(vault_path),
+            backend,
             auto_lock_timeout: Duration::from_secs(300),
             last_activity: Instant::now(),
         }
@@ -62,20 +83,20 @@ impl VaultManager {
     /* ------------------ Vault Creation -------------------------- */
 
     pub fn create_vault(&self, master_password: &str) -> Result<(), VaultError> {
-        if self.storage.vault_exists() {
+        if self.backend.blob_exists() {
             return Err(VaultError::CorruptedVault);
         }
 
-        let vault_data = VaultData::new();
-        self.storage.create_vault(master_password, &vault_data)
+        let vault = Vault::<Plain>::new();
+        storage::create_vault(self.backend.as_ref(), master_password, &vault)
     }
 
     /* ------------------ Unlocking -------------------------- */
 
     pub fn unlock_vault(&mut self, master_password: &str) -> Result<(), VaultError> {
-        let (vault_data, master_key) = self.storage.load_vault(master_password)?;
+        let (vault, master_key) = storage::load_vault(self.backend.as_ref(), master_password)?;
 
-        *self.lock_data()? = Some(vault_data);
+        *self.lock_data()? = Some(vault);
         *self.lock_key()? = Some(master_key);
 
         self.touch();
@@ -106,8 +127,9 @@ impl VaultManager {
             let key_guard = self.lock_key()?;
 
             match (&*data_guard, &*key_guard) {
-                (Some(data), Some(key)) => {
-                    self.storage.save_vault(data, key)?;
+                (Some(vault), Some(key)) => {
+                    let encrypted = vault.encrypt(key)?;
+                    storage::save_vault(self.backend.as_ref(), &encrypted)?;
                 }
                 _ => return Err(VaultError::VaultLocked),
             }
@@ -117,11 +139,57 @@ impl VaultManager {
         Ok(())
     }
 
+    /* ------------------ Master Password Rotation -------------------------- */
+
+    /// Rotates the master password: verifies `current`, derives a fresh
+    /// key (with a brand-new salt) for `new`, re-encrypts the in-memory
+    /// vault under it, and atomically rewrites the file.
+    ///
+    /// The in-memory key is only swapped after the write succeeds, so a
+    /// crash mid-rotation leaves the vault readable under the old password
+    /// rather than unreadable under neither.
+    pub fn change_master_password(&mut self, current: &str, new: &str) -> Result<(), VaultError> {
+        validate_master_password(new)?;
+
+        let data_guard = self.lock_data()?;
+        let mut key_guard = self.lock_key()?;
+
+        let (vault, key) = match (&*data_guard, &*key_guard) {
+            (Some(vault), Some(key)) => (vault, key),
+            _ => return Err(VaultError::VaultLocked),
+        };
+
+        let candidate_key = MasterKey::from_password_with_salt(current, key.salt())?;
+        if !constant_time_compare(candidate_key.key_bytes(), key.key_bytes()) {
+            return Err(VaultError::InvalidPassword);
+        }
+
+        // Best-effort backup of the old-key copy, so a mistyped new
+        // password doesn't lose access to the vault. Only meaningful for
+        // backends with a local path; remote backends skip it.
+        if let Some(path) = self.backend.local_path() {
+            if let Ok(existing) = self.backend.read_blob() {
+                let _ = fs::write(storage::generate_backup_filename(path), existing);
+            }
+        }
+
+        let new_key = MasterKey::from_password(new)?;
+        let encrypted = vault.encrypt(&new_key)?;
+        storage::save_vault(self.backend.as_ref(), &encrypted)?;
+
+        *key_guard = Some(new_key);
+        drop(data_guard);
+        drop(key_guard);
+
+        self.touch();
+        Ok(())
+    }
+
     /* ------------------ Vault Operations -------------------------- */
 
     pub fn with_vault_data<F, T>(&mut self, operation: F) -> Result<T, VaultError>
     where
-        F: FnOnce(&mut VaultData) -> T,
+        F: FnOnce(&mut Vault<Plain>) -> T,
     {
         let result = {
             let mut guard = self.lock_data()?;
@@ -139,6 +207,68 @@ impl VaultManager {
         result
     }
 
+    /* ------------------ Import / Export -------------------------- */
+
+    /// Serialize the currently unlocked entries to `out_path` as `format`.
+    ///
+    /// `confirm_plaintext` must be `true` for formats that always carry
+    /// plaintext secrets (`BitwardenJson`, `CsvPlain`).
+    pub fn export_vault(
+        &self,
+        format: Format,
+        out_path: impl AsRef<Path>,
+        confirm_plaintext: bool,
+    ) -> Result<(), VaultError> {
+        let guard = self.lock_data()?;
+
+        match &*guard {
+            Some(vault) => ImportExport::export(vault.entries(), format, out_path, confirm_plaintext),
+            None => Err(VaultError::VaultLocked),
+        }
+    }
+
+    /// Parse `in_path` as `format` and append the resulting entries to the
+    /// unlocked vault. Returns the number of entries imported.
+    ///
+    /// `confirm_plaintext` must be `true` for formats that always carry
+    /// plaintext secrets (`BitwardenJson`, `CsvPlain`).
+    pub fn import_entries(
+        &mut self,
+        format: Format,
+        in_path: impl AsRef<Path>,
+        confirm_plaintext: bool,
+    ) -> Result<usize, VaultError> {
+        let entries = ImportExport::import(format, in_path, confirm_plaintext)?;
+        let imported = entries.len();
+
+        self.with_vault_data(move |vault| {
+            for entry in entries {
+                vault.add_entry(entry);
+            }
+        })?;
+
+        Ok(imported)
+    }
+
+    /* ------------------ Password Generation -------------------------- */
+
+    /// Generates a password honoring `opts`.
+    ///
+    /// If `min_strength` is given, candidates are regenerated (up to
+    /// `MAX_GENERATION_ATTEMPTS` times) until `estimate_password_strength`
+    /// reaches it, falling back to the last candidate tried.
+    pub fn generate_password(opts: &PasswordOptions, min_strength: Option<f64>) -> SecureString {
+        match min_strength {
+            Some(threshold) => generator::generate_password_above(opts, threshold, MAX_GENERATION_ATTEMPTS),
+            None => generator::generate_password(opts),
+        }
+    }
+
+    /// Generates a diceware-style passphrase of `word_count` words joined by `separator`.
+    pub fn generate_passphrase(word_count: usize, separator: &str) -> SecureString {
+        generator::generate_passphrase(word_count, separator)
+    }
+
     /* ------------------ Auto-lock Policy -------------------------- */
 
     pub fn should_auto_lock(&self) -> bool {
@@ -151,7 +281,7 @@ impl VaultManager {
 
     /* ------------------ Helpers -------------------------- */
 
-    fn lock_data(&self) -> Result<std::sync::MutexGuard<Option<VaultData>>, VaultError> {
+    fn lock_data(&self) -> Result<std::sync::MutexGuard<Option<Vault<Plain>>>, VaultError> {
         self.vault_data.lock().map_err(|_| VaultError::VaultLocked)
     }
 
@@ -163,8 +293,9 @@ impl VaultManager {
         self.last_activity = Instant::now();
     }
 
-    pub fn vault_path(&self) -> &Path {
-        self.storage.path()
+    /// Local filesystem path backing this vault, if the active backend has one.
+    pub fn vault_path(&self) -> Option<&Path> {
+        self.backend.local_path()
     }
 
     pub fn estimate_password_strength(password: &str) -> f64 {
@@ -199,5 +330,12 @@ pub fn validate_master_password(password: &str) -> Result<(), VaultError> {
         return Err(VaultError::InvalidPassword);
     }
 
+    // Character-class diversity alone lets predictable-but-"complex-looking"
+    // passwords through (e.g. a dictionary word plus a trailing digit run),
+    // so also require the pattern-aware strength estimate to clear a floor.
+    if estimate_password_strength(password) < MIN_MASTER_PASSWORD_STRENGTH {
+        return Err(VaultError::InvalidPassword);
+    }
+
     Ok(())
 }
@@ -18,6 +18,9 @@
 //! - Use OS-backed cryptographically secure randomness
 //! - Avoid timing side-channel leaks
 //! - Provide explicit types for material
+//! - Keep key material out of swap for as long as it's held
+
+use std::ops::{Deref, DerefMut};
 
 use rand::distributions::Alphanumeric;
 use rand::RngCore;
@@ -25,6 +28,8 @@ use rand::{rngs::OsRng, Rng};
 use subtle::ConstantTimeEq;
 use zeroize::Zeroizing;
 
+use crate::generator::WORDLIST;
+
 /// Secure container for sensitive UTF-8 string data.
 ///
 /// Automatically zeroize memory when dropped.
@@ -35,6 +40,178 @@ pub type SecureString = Zeroizing<String>;
 /// Automatically zeroizes memory when dropped
 pub type SecureBytes = Zeroizing<Vec<u8>>;
 
+/// Whether a `LockedBytes`/`LockedString` buffer is actually pinned out of
+/// swap, or fell back to an ordinary zeroized buffer because locking
+/// wasn't available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LockState {
+    Locked,
+    Unlocked,
+}
+
+/// A `SecureBytes` buffer additionally locked into physical memory via
+/// `mlock` (`VirtualLock` on Windows), so the OS cannot page it to swap
+/// while it's held.
+///
+/// `Zeroizing` alone only guarantees the contents are wiped on drop; it
+/// says nothing about whether the buffer was written to swap in the
+/// meantime. Locking closes that gap. If locking fails (most commonly
+/// hitting `RLIMIT_MEMLOCK` on Linux), this falls back to an ordinary
+/// zeroized buffer and logs a non-fatal warning rather than erroring out —
+/// the caller still gets a usable secret, just without the extra guarantee.
+pub struct LockedBytes {
+    inner: SecureBytes,
+    state: LockState,
+}
+
+impl LockedBytes {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        let inner = SecureBytes::new(bytes);
+        let state = lock_region(inner.as_ptr(), inner.len());
+        Self { inner, state }
+    }
+
+    /// Whether the OS actually pinned this buffer out of swap.
+    pub fn is_locked(&self) -> bool {
+        self.state == LockState::Locked
+    }
+}
+
+impl Deref for LockedBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.inner
+    }
+}
+
+impl DerefMut for LockedBytes {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.inner
+    }
+}
+
+impl Drop for LockedBytes {
+    fn drop(&mut self) {
+        if self.state == LockState::Locked {
+            unlock_region(self.inner.as_ptr(), self.inner.len());
+        }
+        // `inner`'s own drop (via `Zeroizing`) wipes the contents next.
+    }
+}
+
+/// A `SecureString` buffer additionally locked into physical memory. See
+/// `LockedBytes` for the rationale and fallback behavior.
+pub struct LockedString {
+    inner: SecureString,
+    state: LockState,
+}
+
+impl LockedString {
+    pub fn new(value: String) -> Self {
+        let inner = SecureString::new(value);
+        let state = lock_region(inner.as_ptr(), inner.len());
+        Self { inner, state }
+    }
+
+    /// Whether the OS actually pinned this buffer out of swap.
+    pub fn is_locked(&self) -> bool {
+        self.state == LockState::Locked
+    }
+}
+
+impl Deref for LockedString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.inner.as_str()
+    }
+}
+
+impl Drop for LockedString {
+    fn drop(&mut self) {
+        if self.state == LockState::Locked {
+            unlock_region(self.inner.as_ptr(), self.inner.len());
+        }
+        // `inner`'s own drop (via `Zeroizing`) wipes the contents next.
+    }
+}
+
+/// Locks the `len` bytes starting at `ptr` into physical memory, falling
+/// back gracefully (with a logged warning) if the platform doesn't
+/// support it or the call fails, e.g. due to `RLIMIT_MEMLOCK`.
+#[cfg(unix)]
+fn lock_region(ptr: *const u8, len: usize) -> LockState {
+    if len == 0 {
+        return LockState::Locked;
+    }
+
+    let result = unsafe { libc::mlock(ptr as *const libc::c_void, len) };
+
+    if result == 0 {
+        LockState::Locked
+    } else {
+        eprintln!(
+            "warning: failed to lock a {len}-byte sensitive buffer into memory; \
+             it may be written to swap (check RLIMIT_MEMLOCK)"
+        );
+        LockState::Unlocked
+    }
+}
+
+#[cfg(unix)]
+fn unlock_region(ptr: *const u8, len: usize) {
+    if len == 0 {
+        return;
+    }
+
+    unsafe {
+        libc::munlock(ptr as *const libc::c_void, len);
+    }
+}
+
+#[cfg(windows)]
+fn lock_region(ptr: *const u8, len: usize) -> LockState {
+    if len == 0 {
+        return LockState::Locked;
+    }
+
+    let result = unsafe { winapi::um::memoryapi::VirtualLock(ptr as *mut _, len) };
+
+    if result != 0 {
+        LockState::Locked
+    } else {
+        eprintln!(
+            "warning: failed to lock a {len}-byte sensitive buffer into memory; \
+             it may be written to the page file"
+        );
+        LockState::Unlocked
+    }
+}
+
+#[cfg(windows)]
+fn unlock_region(ptr: *const u8, len: usize) {
+    if len == 0 {
+        return;
+    }
+
+    unsafe {
+        winapi::um::memoryapi::VirtualUnlock(ptr as *mut _, len);
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn lock_region(_ptr: *const u8, len: usize) -> LockState {
+    eprintln!(
+        "warning: memory locking is not supported on this platform; a {len}-byte sensitive \
+         buffer may be written to swap"
+    );
+    LockState::Unlocked
+}
+
+#[cfg(not(any(unix, windows)))]
+fn unlock_region(_ptr: *const u8, _len: usize) {}
+
 /// Generates a cryptographic secure random alphanumeric string.
 ///
 /// Use `OsRng` to ensure randomness originates from the operating system CSPRNG
@@ -42,7 +219,7 @@ pub type SecureBytes = Zeroizing<Vec<u8>>;
 /// # Security NOtes:
 /// - Intended for temporary secrets, tokens, or generated passwords
 /// - Avoid using this for key material (use raw bytes instead)
-pub fn generate_secure_random_string(length: usize) -> SecureString {
+pub fn generate_secure_random_string(length: usize) -> LockedString {
     let mut rng = OsRng;
 
     let random_string: String = rng
@@ -51,19 +228,80 @@ pub fn generate_secure_random_string(length: usize) -> SecureString {
         .map(char::from)
         .collect();
 
-    SecureString::new(random_string)
+    LockedString::new(random_string)
 }
 
 /// Generates cryptographically secure random bytes
 ///
 /// Preferred for cryptographic key material
-pub fn generate_secure_random_bytes(length: usize) -> SecureBytes {
+pub fn generate_secure_random_bytes(length: usize) -> LockedBytes {
     let mut rng = OsRng;
     let mut buffer = vec![0u8; length];
 
     rng.fill_bytes(&mut buffer);
 
-    SecureBytes::new(buffer)
+    LockedBytes::new(buffer)
+}
+
+/// Draws a uniform index in `[0, bound)` from `OsRng` via rejection
+/// sampling, rather than `candidate % bound`, which is biased towards the
+/// low end whenever `bound` doesn't evenly divide `u32::MAX + 1`.
+fn uniform_index(bound: usize) -> usize {
+    assert!(bound > 0, "uniform_index bound must be positive");
+
+    let bound = bound as u32;
+    let limit = u32::MAX - (u32::MAX % bound);
+
+    let mut rng = OsRng;
+    loop {
+        let candidate = rng.next_u32();
+        if candidate < limit {
+            return (candidate % bound) as usize;
+        }
+    }
+}
+
+/// Estimated entropy, in bits, of a passphrase of `word_count` words drawn
+/// uniformly from the embedded word list.
+pub fn passphrase_entropy_bits(word_count: usize) -> f64 {
+    word_count as f64 * (WORDLIST.len() as f64).log2()
+}
+
+/// Generates a diceware-style passphrase of `word_count` words drawn
+/// uniformly from an embedded EFF-style word list and joined by
+/// `separator`, for use as a memorable master password.
+///
+/// Each word index is drawn via `uniform_index` rather than
+/// `index % WORDLIST.len()`, so the distribution stays unbiased regardless
+/// of how the list length divides the RNG's output range.
+///
+/// When `append_policy_suffix` is set, a single random digit or symbol is
+/// appended so the result satisfies character-class policy checks (e.g.
+/// `validate_master_password`) that a pure word list can't.
+///
+/// Returns the passphrase alongside its estimated entropy in bits, so
+/// callers can feed it into `estimate_password_strength` or show the
+/// number to the user directly instead of re-deriving it.
+pub fn generate_secure_passphrase(
+    word_count: usize,
+    separator: &str,
+    append_policy_suffix: bool,
+) -> (SecureString, f64) {
+    const SUFFIX_CHARS: &[u8] = b"0123456789!@#$%^&*";
+
+    let words: Vec<&str> = (0..word_count)
+        .map(|_| WORDLIST[uniform_index(WORDLIST.len())])
+        .collect();
+
+    let mut passphrase = words.join(separator);
+    let mut entropy_bits = passphrase_entropy_bits(word_count);
+
+    if append_policy_suffix {
+        passphrase.push(SUFFIX_CHARS[uniform_index(SUFFIX_CHARS.len())] as char);
+        entropy_bits += (SUFFIX_CHARS.len() as f64).log2();
+    }
+
+    (SecureString::new(passphrase), entropy_bits)
 }
 
 /// Constant time comparison of two byte slices
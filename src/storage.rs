@@ -14,118 +14,298 @@
 //! Vault file persistence layer
 //!
 //! Responsibilities:
-//! - Atomic vault writes
-//! - Encryption orchestration
-//! - Vault loading and validation
+//! - Encrypt/decrypt orchestration for the vault container
+//! - Byte-level transport is delegated to a pluggable `VaultBackend`
 //!
 //! Security goals:
-//! - Prevent corruption on crash/power loss
+//! - Prevent corruption on crash/power loss (local backend)
 //! - Avoid plaintext memory persistence
 //! - Maintain audit-friendly behaviour
 
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
-use zeroize::Zeroizing;
-
-use crate::crypto::{EncryptedVault, MasterKey};
+use crate::crypto::{EncryptedVault, KdfParams, MasterKey};
 use crate::errors::VaultError;
-use crate::memory::SecureBytes;
-use crate::vault::VaultData;
+use crate::vault::{Encrypted, Plain, Vault};
 
-/// File extension for vault files
+/// File extension for local vault files.
 pub const VAULT_EXTENSION: &str = ".sark";
 
-/// Handle vault file operations.
-pub struct VaultStorage {
+/* ------------------- Backend trait ----------------------- */
+
+/// Byte-level transport for an encrypted vault container.
+///
+/// Implementations only move opaque bytes; they have no knowledge of the
+/// encryption scheme or vault schema. This keeps the encrypted container
+/// storage-agnostic, so a vault can live on local disk, in memory, or in
+/// a remote object store.
+pub trait VaultBackend: Send + Sync {
+    /// Read the full encrypted blob.
+    fn read_blob(&self) -> Result<Vec<u8>, VaultError>;
+
+    /// Overwrite the encrypted blob in full.
+    fn write_blob(&self, data: &[u8]) -> Result<(), VaultError>;
+
+    /// Whether a blob currently exists for this backend.
+    fn blob_exists(&self) -> bool;
+
+    /// Delete the blob, if present.
+    fn delete_blob(&self) -> Result<(), VaultError>;
+
+    /// Local filesystem path backing this blob, if any.
+    ///
+    /// Remote backends (object stores, etc.) have no meaningful path and
+    /// should keep the default `None`.
+    fn local_path(&self) -> Option<&Path> {
+        None
+    }
+}
+
+/* ------------------- Local filesystem backend ----------------------- */
+
+/// Stores the encrypted vault as a single file on local disk.
+///
+/// Writes are atomic: the new contents land in a temp file first, which
+/// is then renamed over the real path, so a crash mid-write cannot leave
+/// a half-written vault behind.
+pub struct LocalFileBackend {
     vault_path: PathBuf,
 }
 
-impl VaultStorage {
+impl LocalFileBackend {
     pub fn new(path: impl AsRef<Path>) -> Self {
         Self {
             vault_path: path.as_ref().to_path_buf(),
         }
     }
+}
 
-    /* ------------------- Vault Creation --------------- */
-    pub fn create_vault(
-        &self,
-        master_password: &str,
-        vault_data: &VaultData,
-    ) -> Result<(), VaultError> {
-        let master_key = MasterKey::from_password(master_password)?;
-        self.save_vault(vault_data, &master_key)
+impl VaultBackend for LocalFileBackend {
+    fn read_blob(&self) -> Result<Vec<u8>, VaultError> {
+        fs::read(&self.vault_path).map_err(VaultError::IoError)
     }
 
-    /* --------------------- Vault Loading --------------------- */
-    pub fn load_vault(&self, master_password: &str) -> Result<(VaultData, MasterKey), VaultError> {
-        let vault_bytes = fs::read(&self.vault_path).map_err(VaultError::IoError)?;
-
-        let encrypted_vault =
-            EncryptedVault::from_bytes(&vault_bytes).map_err(|_| VaultError::SerializationError)?;
+    fn write_blob(&self, data: &[u8]) -> Result<(), VaultError> {
+        let temp_path = self.vault_path.with_extension("tmp");
 
-        // Validate version early
-        if encrypted_vault.version != EncryptedVault::CURRENT_VERSION {
-            return Err(VaultError::CorruptedVault);
+        {
+            let mut file = fs::File::create(&temp_path).map_err(VaultError::IoError)?;
+            file.write_all(data).map_err(VaultError::IoError)?;
+            file.sync_all().map_err(VaultError::IoError)?;
         }
 
-        let master_key =
-            MasterKey::from_password_with_salt(master_password, &encrypted_vault.salt)?;
+        fs::rename(temp_path, &self.vault_path).map_err(VaultError::IoError)?;
+        Ok(())
+    }
 
-        let decrypted_bytes = encrypted_vault.decrypt(&master_key)?;
+    fn blob_exists(&self) -> bool {
+        self.vault_path.exists()
+    }
 
-        let vault_data: VaultData =
-            bincode::deserialize(&decrypted_bytes).map_err(|_| VaultError::SerializationError)?;
+    fn delete_blob(&self) -> Result<(), VaultError> {
+        fs::remove_file(&self.vault_path).map_err(VaultError::IoError)
+    }
 
-        Ok((vault_data, master_key))
+    fn local_path(&self) -> Option<&Path> {
+        Some(&self.vault_path)
     }
+}
 
-    /* ------------------- Vault Save ----------------------- */
-    pub fn save_vault(
-        &self,
-        vault_data: &VaultData,
-        master_key: &MasterKey,
-    ) -> Result<(), VaultError> {
-        // Serialize plaintext vault into zeroizing buffer
-        let serialized = Zeroizing::new(
-            bincode::serialize(vault_data).map_err(|_| VaultError::SerializationError)?,
-        );
+/* ------------------- In-memory backend (tests) ----------------------- */
 
-        let encrypted_vault = EncryptedVault::encrypt(&serialized, master_key)?;
+/// Holds the encrypted blob in memory; never touches disk.
+///
+/// Intended for unit/integration tests that want to exercise the
+/// save/load round trip without creating temp files.
+#[derive(Default)]
+pub struct InMemoryBackend {
+    blob: Mutex<Option<Vec<u8>>>,
+}
 
-        let vault_bytes = encrypted_vault
-            .to_bytes()
-            .map_err(|_| VaultError::SerializationError)?;
+impl InMemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
 
-        self.atomic_write(&vault_bytes)
+impl VaultBackend for InMemoryBackend {
+    fn read_blob(&self) -> Result<Vec<u8>, VaultError> {
+        self.blob
+            .lock()
+            .map_err(|_| VaultError::CryptoError)?
+            .clone()
+            .ok_or_else(|| VaultError::IoError(std::io::Error::from(std::io::ErrorKind::NotFound)))
     }
 
-    /* ------------------- Atomic Write ----------------------- */
-    fn atomic_write(&self, data: &[u8]) -> Result<(), VaultError> {
-        let temp_path = self.vault_path.with_extension("tmp");
+    fn write_blob(&self, data: &[u8]) -> Result<(), VaultError> {
+        *self.blob.lock().map_err(|_| VaultError::CryptoError)? = Some(data.to_vec());
+        Ok(())
+    }
 
-        {
-            let mut file = fs::File::create(&temp_path).map_err(VaultError::IoError)?;
-            file.write_all(data).map_err(VaultError::IoError)?;
-            file.sync_all().map_err(VaultError::IoError)?;
-        }
+    fn blob_exists(&self) -> bool {
+        self.blob.lock().map(|b| b.is_some()).unwrap_or(false)
+    }
 
-        fs::rename(temp_path, &self.vault_path).map_err(VaultError::IoError)?;
+    fn delete_blob(&self) -> Result<(), VaultError> {
+        *self.blob.lock().map_err(|_| VaultError::CryptoError)? = None;
         Ok(())
     }
+}
 
-    /* ------------------- Helpers ----------------------- */
-    pub fn vault_exists(&self) -> bool {
-        self.vault_path.exists()
+/* ------------------- Object store backend (scaffold) ----------------------- */
+
+/// Minimal client surface an object-store backend needs: get/put/delete
+/// a single object by key. A real implementation would wrap an S3 (or
+/// compatible) SDK client behind this trait.
+pub trait ObjectStoreClient: Send + Sync {
+    fn get_object(&self, bucket: &str, key: &str) -> Result<Vec<u8>, VaultError>;
+    fn put_object(&self, bucket: &str, key: &str, data: &[u8]) -> Result<(), VaultError>;
+    fn object_exists(&self, bucket: &str, key: &str) -> bool;
+    fn delete_object(&self, bucket: &str, key: &str) -> Result<(), VaultError>;
+}
+
+/// Scaffold for storing the encrypted vault blob in an S3-compatible
+/// object store. Wire `client` to a real SDK-backed `ObjectStoreClient`
+/// to point a vault at a remote bucket.
+pub struct ObjectStoreBackend {
+    client: Box<dyn ObjectStoreClient>,
+    bucket: String,
+    key: String,
+}
+
+impl ObjectStoreBackend {
+    pub fn new(client: Box<dyn ObjectStoreClient>, bucket: impl Into<String>, key: impl Into<String>) -> Self {
+        Self {
+            client,
+            bucket: bucket.into(),
+            key: key.into(),
+        }
+    }
+}
+
+impl VaultBackend for ObjectStoreBackend {
+    fn read_blob(&self) -> Result<Vec<u8>, VaultError> {
+        self.client.get_object(&self.bucket, &self.key)
+    }
+
+    fn write_blob(&self, data: &[u8]) -> Result<(), VaultError> {
+        self.client.put_object(&self.bucket, &self.key, data)
+    }
+
+    fn blob_exists(&self) -> bool {
+        self.client.object_exists(&self.bucket, &self.key)
     }
 
-    pub fn path(&self) -> &Path {
-        &self.vault_path
+    fn delete_blob(&self) -> Result<(), VaultError> {
+        self.client.delete_object(&self.bucket, &self.key)
     }
 }
 
+/* ------------------- Encryption orchestration ----------------------- */
+
+/// Create a vault: derive a key from `master_password` and save an empty
+/// vault through `backend`.
+pub fn create_vault(
+    backend: &dyn VaultBackend,
+    master_password: &str,
+    vault: &Vault<Plain>,
+) -> Result<(), VaultError> {
+    let master_key = MasterKey::from_password(master_password)?;
+    let encrypted = vault.encrypt(&master_key)?;
+    save_vault(backend, &encrypted)
+}
+
+/// Load the vault behind `backend` and decrypt it with `master_password`.
+///
+/// Two kinds of transparent upgrade can happen here, both gated on a
+/// successful, authenticated decrypt having already occurred so a
+/// tampered or forged container can't trigger either one:
+/// - an older format version is migrated to `EncryptedVault::CURRENT_VERSION`
+/// - KDF parameters weaker than `KdfParams::CURRENT` are re-derived under
+///   the stronger ones
+///
+/// Either case rewrites the vault through `backend` before returning.
+pub fn load_vault(
+    backend: &dyn VaultBackend,
+    master_password: &str,
+) -> Result<(Vault<Plain>, MasterKey), VaultError> {
+    let vault_bytes = backend.read_blob()?;
+
+    let container = EncryptedVault::from_bytes(&vault_bytes)?;
+    let stored_version = container.version();
+    let stored_params = container.kdf_params();
+
+    let master_key =
+        MasterKey::from_password_with_params(master_password, container.salt(), stored_params)?;
+
+    let encrypted = Vault::<Encrypted>::from_container(container);
+    let plain = encrypted.decrypt(&master_key)?;
+
+    let needs_migration = stored_version < EncryptedVault::CURRENT_VERSION;
+    let needs_kdf_upgrade = stored_params.weaker_than(KdfParams::CURRENT);
+
+    if !needs_migration && !needs_kdf_upgrade {
+        return Ok((plain, master_key));
+    }
+
+    let migrated = if needs_migration {
+        migrate_to_current(plain, stored_version)
+    } else {
+        plain
+    };
+
+    let active_key = if needs_kdf_upgrade {
+        MasterKey::from_password_with_params(master_password, master_key.salt(), KdfParams::CURRENT)?
+    } else {
+        master_key
+    };
+
+    let reencrypted = migrated.encrypt(&active_key)?;
+    save_vault(backend, &reencrypted)?;
+
+    Ok((migrated, active_key))
+}
+
+/* ------------------- Versioned format migrations ----------------------- */
+
+/// One step in the migration chain: transforms a vault already decrypted
+/// from the version immediately before it. Keyed by the version it
+/// upgrades *to*.
+type MigrationStep = fn(Vault<Plain>) -> Vault<Plain>;
+
+/// Registered in ascending version order. Empty today — the format has
+/// only ever had one version — but `load_vault` already walks this chain,
+/// so a future version bump just adds an entry here.
+const MIGRATIONS: &[(u16, MigrationStep)] = &[];
+
+/// Applies every migration step after `from_version`, in order, returning
+/// a vault ready to be re-encrypted at `EncryptedVault::CURRENT_VERSION`.
+fn migrate_to_current(mut vault: Vault<Plain>, from_version: u16) -> Vault<Plain> {
+    for (version, step) in MIGRATIONS {
+        if *version > from_version {
+            vault = step(vault);
+        }
+    }
+
+    vault
+}
+
+/// Write an already-encrypted vault through `backend`.
+///
+/// Only a `Vault<Encrypted>` can reach this function, so there is no code
+/// path that hands a backend plaintext by accident.
+pub fn save_vault(backend: &dyn VaultBackend, encrypted: &Vault<Encrypted>) -> Result<(), VaultError> {
+    let vault_bytes = encrypted
+        .container()
+        .to_bytes()
+        .map_err(|_| VaultError::SerializationError)?;
+
+    backend.write_blob(&vault_bytes)
+}
+
 /* ------------------- Backup Filename Utility ----------------------- */
 pub fn generate_backup_filename(base_path: &Path) -> PathBuf {
     use chrono::Local;
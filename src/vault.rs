@@ -12,20 +12,25 @@
 // MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
 
 //! Core vault data structures
-//! 
-//! This module defines the plaintext in-memory representation of vault data
-//! The entire structure is expected to be serialized and encrypted as a single unit
-//! 
+//!
+//! This module defines the plaintext in-memory representation of vault data,
+//! and the type-state `Vault<State>` wrapper that tracks whether that data
+//! is currently plaintext or encrypted.
+//!
 //! Security goals:
 //! - Sensitive fields are zeroized on drop
 //! - Schema is versioned for forward compatibility
 //! - Minimal accidental data leakage
-//! - Audit-friendly and explicit behaviour
+//! - Only a `Vault<Plain>` exposes entry accessors; only a `Vault<Encrypted>`
+//!   can be handed to a `VaultBackend` for persistence
+
+use std::marker::PhantomData;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use zeroize::{Zeroize, ZeroizeOnDrop};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
+use crate::crypto::{EncryptedVault, MasterKey};
 use crate::errors::VaultError;
 
 /// Represent a single credential stored in the vault
@@ -55,17 +60,12 @@ pub struct PasswordEntry {
 
     /// Last modification timestamp
     #[zeroize(skip)]
-    pub updated_at: DateTime<Utc>
+    pub updated_at: DateTime<Utc>,
 }
 
 impl PasswordEntry {
     /// Create a new vault entry with current timestamp
-    pub fn new(
-        account_name: String,
-        username: String,
-        password: String,
-        notes: String,
-    ) -> Self {
+    pub fn new(account_name: String, username: String, password: String, notes: String) -> Self {
         let now = Utc::now();
 
         Self {
@@ -73,13 +73,13 @@ impl PasswordEntry {
             username,
             password,
             notes,
-            created_at,
-            updated_at,
+            created_at: now,
+            updated_at: now,
         }
     }
 
     /// Updates selected fields of an entry
-    /// 
+    ///
     /// The timestamp is automatically refreshed if any field changes
     pub fn update(
         &mut self,
@@ -117,8 +117,11 @@ impl PasswordEntry {
 }
 
 /// Top-level plaintext vault container
-/// 
-/// This structure is serialized and encrypted as a unit
+///
+/// This structure is serialized and encrypted as a unit. Its constructor
+/// and entry methods are private to this module, so entries are managed
+/// through `Vault<Plain>`, and `storage::save_vault` only accepts a
+/// `Vault<Encrypted>`.
 #[derive(Debug, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
 pub struct VaultData {
     /// Schema version for forward compatibility
@@ -136,12 +139,11 @@ pub struct VaultData {
     pub last_modified: DateTime<Utc>,
 }
 
-impl Vault {
+impl VaultData {
     /// Current vault schema version
     pub const CURRENT_VERSION: u32 = 1;
 
-    /// Creates an empty vault
-    pub fn new() -> Self {
+    fn new() -> Self {
         let now = Utc::now();
 
         Self {
@@ -152,25 +154,22 @@ impl Vault {
         }
     }
 
-    // Adds a new entry to the vault
-    pub fn add_entry(&mut self, entry: PasswordEntry) {
+    fn add_entry(&mut self, entry: PasswordEntry) {
         self.entries.push(entry);
         self.touch();
     }
 
-    /// Removes an entry by index
-    pub fn remove_entry(&mut self, index: usize) -> Result<PasswordEntry, VaultError> {
+    fn remove_entry(&mut self, index: usize) -> Result<PasswordEntry, VaultError> {
         if index >= self.entries.len() {
             return Err(VaultError::EntryNotFound);
         }
 
         let removed = self.entries.remove(index);
         self.touch();
-        ok(removed)
+        Ok(removed)
     }
 
-    /// Updates an entry by index
-    pub fn update_entry(
+    fn update_entry(
         &mut self,
         index: usize,
         account_name: Option<String>,
@@ -178,40 +177,176 @@ impl Vault {
         password: Option<String>,
         notes: Option<String>,
     ) -> Result<(), VaultError> {
-        let entry = self
-        .entries
-        .get_mut(index)
-        .ok_or(VaultError::EntryNotFound)?;
+        let entry = self.entries.get_mut(index).ok_or(VaultError::EntryNotFound)?;
 
-    entry.update(account_name, username, password, notes);
-    self.touch();
+        entry.update(account_name, username, password, notes);
+        self.touch();
 
-    Ok(())
+        Ok(())
     }
 
-    /// Retrieves an entry by index
-    pub fn get_entry(&self, index: usize) -> Option<PasswordEntry> {
-        self.entries.get(index)
+    fn get_entry(&self, index: usize) -> Option<PasswordEntry> {
+        self.entries.get(index).cloned()
     }
 
     /// Case-insensitive search across selected fields
-    /// 
+    ///
     /// This operates on decrypted in-memory data only
-    pub fn find_entries(&self, query: &str) -> Vec<&PasswordEntry> {
+    fn find_entries(&self, query: &str) -> Vec<&PasswordEntry> {
         let query_lower = query.to_lowercase();
 
         self.entries
-        .iter()
-        .filter(|entry| {
-            entry.account_name.to_lowercase().contains(&query_lower)
-            || entry.username.to_lowercase().contains(&query_lower)
-            || entry.notes.to_lowercase().contains(&query_lower)
-        })
-        .collect()
+            .iter()
+            .filter(|entry| {
+                entry.account_name.to_lowercase().contains(&query_lower)
+                    || entry.username.to_lowercase().contains(&query_lower)
+                    || entry.notes.to_lowercase().contains(&query_lower)
+            })
+            .collect()
     }
 
     /// Updates the vault-level modification timestamp
     fn touch(&mut self) {
         self.last_modified = Utc::now();
     }
-}
\ No newline at end of file
+}
+
+/* ------------------ Type-state Vault Wrapper -------------------------- */
+
+/// Marker type: the vault holds live plaintext entries in memory.
+pub struct Plain;
+
+/// Marker type: the vault holds an opaque, AEAD-encrypted container.
+pub struct Encrypted;
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for super::Plain {}
+    impl Sealed for super::Encrypted {}
+}
+
+/// Associates each type-state marker with what it actually stores.
+pub trait VaultState: private::Sealed {
+    #[doc(hidden)]
+    type Repr;
+}
+
+impl VaultState for Plain {
+    type Repr = VaultData;
+}
+
+impl VaultState for Encrypted {
+    type Repr = EncryptedVault;
+}
+
+/// A vault container whose encryption state is tracked in the type system.
+///
+/// `Vault<Plain>` exposes entry accessors (`add_entry`, `find_entries`, ...)
+/// and can `encrypt()` into a `Vault<Encrypted>`. `Vault<Encrypted>` exposes
+/// only the encrypted container and can `decrypt()` back into a
+/// `Vault<Plain>`. There is no path from `Vault<Plain>` to a `VaultBackend`
+/// that skips `encrypt()`, so plaintext-at-rest is unrepresentable.
+pub struct Vault<S: VaultState> {
+    inner: S::Repr,
+    _state: PhantomData<S>,
+}
+
+impl Vault<Plain> {
+    /// Creates an empty plaintext vault.
+    pub fn new() -> Self {
+        Self {
+            inner: VaultData::new(),
+            _state: PhantomData,
+        }
+    }
+
+    /// Adds a new entry to the vault.
+    pub fn add_entry(&mut self, entry: PasswordEntry) {
+        self.inner.add_entry(entry);
+    }
+
+    /// Removes an entry by index.
+    pub fn remove_entry(&mut self, index: usize) -> Result<PasswordEntry, VaultError> {
+        self.inner.remove_entry(index)
+    }
+
+    /// Updates an entry by index.
+    pub fn update_entry(
+        &mut self,
+        index: usize,
+        account_name: Option<String>,
+        username: Option<String>,
+        password: Option<String>,
+        notes: Option<String>,
+    ) -> Result<(), VaultError> {
+        self.inner
+            .update_entry(index, account_name, username, password, notes)
+    }
+
+    /// Retrieves an entry by index.
+    pub fn get_entry(&self, index: usize) -> Option<PasswordEntry> {
+        self.inner.get_entry(index)
+    }
+
+    /// Case-insensitive search across selected fields.
+    pub fn find_entries(&self, query: &str) -> Vec<&PasswordEntry> {
+        self.inner.find_entries(query)
+    }
+
+    /// All entries currently held in the vault.
+    pub fn entries(&self) -> &[PasswordEntry] {
+        &self.inner.entries
+    }
+
+    /// Encrypts this vault under `master_key`, producing a persistable
+    /// `Vault<Encrypted>`. This is the only way to turn plaintext into
+    /// something a `VaultBackend` will accept.
+    pub fn encrypt(&self, master_key: &MasterKey) -> Result<Vault<Encrypted>, VaultError> {
+        let serialized = Zeroizing::new(
+            bincode::serialize(&self.inner).map_err(|_| VaultError::SerializationError)?,
+        );
+
+        Ok(Vault {
+            inner: EncryptedVault::encrypt(&serialized, master_key)?,
+            _state: PhantomData,
+        })
+    }
+}
+
+impl Default for Vault<Plain> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Vault<Encrypted> {
+    /// Wraps an already-loaded encrypted container.
+    pub fn from_container(container: EncryptedVault) -> Self {
+        Self {
+            inner: container,
+            _state: PhantomData,
+        }
+    }
+
+    /// The underlying encrypted container, ready for `VaultBackend::write_blob`.
+    pub fn container(&self) -> &EncryptedVault {
+        &self.inner
+    }
+
+    pub fn into_container(self) -> EncryptedVault {
+        self.inner
+    }
+
+    /// Decrypts this vault under `master_key`, producing a usable `Vault<Plain>`.
+    pub fn decrypt(&self, master_key: &MasterKey) -> Result<Vault<Plain>, VaultError> {
+        let plaintext = self.inner.decrypt(master_key)?;
+
+        let data: VaultData =
+            bincode::deserialize(&plaintext).map_err(|_| VaultError::SerializationError)?;
+
+        Ok(Vault {
+            inner: data,
+            _state: PhantomData,
+        })
+    }
+}
@@ -0,0 +1,350 @@
+#[cfg(test)]
+mod tests {
+    use ferreus_vault::*;
+    use serial_test::serial;
+    use tempfile::NamedTempFile;
+
+    /* ----------------------------------------Vault LifeCycle -------------------------- */
+
+    #[test]
+    #[serial]
+    fn vault_creation_unlock_and_lock_cycle() {
+        let temp = NamedTempFile::new().expect("temp file");
+        let path = temp.path();
+        // Free the placeholder file: `create_vault` won't write over an
+        // existing blob. Dropping `temp` still removes the vault afterwards.
+        std::fs::remove_file(path).unwrap();
+
+        let mut manager = VaultManager::new(path);
+
+        let strong = "Vq7#Lm2$Xr9&Tz4!Kw";
+
+        assert!(validate_master_password("weak").is_err());
+        assert!(validate_master_password(strong).is_ok());
+
+        manager.create_vault(strong).expect("create vault");
+
+        assert!(!manager.is_unlocked());
+
+        assert!(manager.unlock_vault("WrongPassword").is_err());
+
+        manager.unlock_vault(strong).expect("unlock");
+        assert!(manager.is_unlocked());
+
+        manager.lock_vault();
+        assert!(!manager.is_unlocked());
+    }
+
+    /* ----------------------------------------Entry Persistence -------------------------- */
+    #[test]
+    #[serial]
+    fn entry_create_update_and_persist() {
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path();
+        // Free the placeholder file: `create_vault` won't write over it.
+        std::fs::remove_file(path).unwrap();
+
+        let mut manager = VaultManager::new(path);
+        let password = "TestPassword123!@#";
+
+        manager.create_vault(password).unwrap();
+        manager.unlock_vault(password).unwrap();
+
+        manager
+            .with_vault_data(|vault| {
+                vault.add_entry(vault::PasswordEntry::new(
+                    "Gmail".into(),
+                    "user@gmail.com".into(),
+                    "secret".into(),
+                    "notes".into(),
+                ));
+            })
+            .unwrap();
+
+        manager
+            .with_vault_data(|vault| {
+                vault
+                    .update_entry(
+                        0,
+                        Some("Google Mail".into()),
+                        Some("new@gmail.com".into()),
+                        Some("newpass".into()),
+                        Some("updated".into()),
+                    )
+                    .unwrap();
+            })
+            .unwrap();
+
+        manager.save_vault().unwrap();
+        manager.lock_vault();
+        manager.unlock_vault(password).unwrap();
+
+        let name = manager
+            .with_vault_data(|vault| vault.get_entry(0).unwrap().account_name.clone())
+            .unwrap();
+
+        assert_eq!(name, "Google Mail");
+    }
+
+    /* ----------------------------------------Temper Detection -------------------------- */
+
+    #[test]
+    #[serial]
+    fn tampered_vault_rejected() {
+        use std::fs;
+
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path();
+        // Free the placeholder file: `create_vault` won't write over it.
+        fs::remove_file(path).unwrap();
+
+        let mut manager = VaultManager::new(path);
+        let password = "TamperTestPassword123!";
+
+        manager.create_vault(password).unwrap();
+        manager.unlock_vault(password).unwrap();
+        manager.save_vault().unwrap();
+
+        // Corrupt vault file
+        let mut bytes = fs::read(path).unwrap();
+        bytes[bytes.len() / 2] ^= 0xFF;
+        fs::write(path, bytes).unwrap();
+
+        assert!(manager.unlock_vault(password).is_err());
+    }
+
+    /* ----------------------------------------Password Strength Heuristic-------------------------- */
+
+    #[test]
+    fn password_strength_scoring() {
+        assert!(crypto::estimate_password_strength("password") < 30.0);
+        // A dictionary word plus a trailing ascending sequence collapses to
+        // very few guesses despite "looking" complex.
+        assert!(crypto::estimate_password_strength("Password123") < 50.0);
+        assert!(crypto::estimate_password_strength("Very$tr0ngP@ssw0rd!WithManyChars") > 80.0);
+    }
+
+    /* ----------------------------------------Auto Lock-------------------------- */
+
+    #[test]
+    #[serial]
+    fn auto_lock_trigger_behaviour() {
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path();
+        // Free the placeholder file: `create_vault` won't write over it.
+        std::fs::remove_file(path).unwrap();
+
+        let mut manager = VaultManager::new(path);
+        let password = "AutoLockPassword123!";
+
+        manager.create_vault(password).unwrap();
+        manager.unlock_vault(password).unwrap();
+
+        manager.set_auto_lock_timeout(std::time::Duration::from_millis(100));
+
+        std::thread::sleep(std::time::Duration::from_millis(150));
+
+        assert!(manager.should_auto_lock());
+
+        manager.lock_vault();
+        assert!(!manager.is_unlocked());
+    }
+
+    /* ----------------------------------------Secure Random Generation  -------------------------- */
+
+    #[test]
+    fn secure_random_generation() {
+        use ferreus_vault::memory::generate_secure_random_string;
+
+        let random = generate_secure_random_string(32);
+
+        assert_eq!(random.len(), 32);
+        assert!(random.chars().all(|c| c.is_alphanumeric()));
+    }
+
+    /* ----------------------------------------Master Password Rotation -------------------------- */
+
+    #[test]
+    #[serial]
+    fn master_password_rotation_reencrypts_and_unlocks_under_new_password() {
+        use std::fs;
+
+        // `NamedTempFile` creates its placeholder file immediately, and
+        // `create_vault` refuses to write over an existing blob, so the
+        // reserved path is freed before the vault claims it.
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path().to_path_buf();
+        temp.close().unwrap();
+
+        let mut manager = VaultManager::new(&path);
+        let current = "RotateCurrentPassword123!@#";
+        let new = "Nw4%Jd8^Hs6*Bp3@Gy";
+
+        manager.create_vault(current).unwrap();
+        manager.unlock_vault(current).unwrap();
+
+        manager.change_master_password(current, new).unwrap();
+
+        manager.lock_vault();
+        assert!(manager.unlock_vault(current).is_err());
+
+        manager.unlock_vault(new).expect("vault should unlock under the rotated password");
+        assert!(manager.is_unlocked());
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    #[serial]
+    fn master_password_rotation_rejects_wrong_current_password() {
+        use std::fs;
+
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path().to_path_buf();
+        temp.close().unwrap();
+
+        let mut manager = VaultManager::new(&path);
+        let current = "RotateCurrentPassword123!@#";
+        let new = "Nw4%Jd8^Hs6*Bp3@Gy";
+
+        manager.create_vault(current).unwrap();
+        manager.unlock_vault(current).unwrap();
+        manager.save_vault().unwrap();
+
+        let before = fs::read(&path).unwrap();
+
+        let result = manager.change_master_password("WrongCurrentPassword!@#", new);
+        assert!(matches!(result, Err(errors::VaultError::InvalidPassword)));
+
+        // A rejected rotation must leave the on-disk vault untouched.
+        assert_eq!(fs::read(&path).unwrap(), before);
+
+        manager.lock_vault();
+        manager.unlock_vault(current).expect("original password must still unlock");
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    #[serial]
+    fn master_password_rotation_writes_pre_rotation_backup() {
+        use std::collections::HashSet;
+        use std::fs;
+
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path().to_path_buf();
+        temp.close().unwrap();
+        let dir = path.parent().unwrap().to_path_buf();
+
+        let mut manager = VaultManager::new(&path);
+        let current = "RotateCurrentPassword123!@#";
+        let new = "Nw4%Jd8^Hs6*Bp3@Gy";
+
+        manager.create_vault(current).unwrap();
+        manager.unlock_vault(current).unwrap();
+        manager.save_vault().unwrap();
+
+        let before: HashSet<_> = fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .collect();
+
+        manager.change_master_password(current, new).unwrap();
+
+        let after: HashSet<_> = fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .collect();
+
+        let backup = after
+            .difference(&before)
+            .find(|p| p.to_string_lossy().contains("_back"));
+
+        assert!(
+            backup.is_some(),
+            "rotation should leave a pre-rotation backup file behind"
+        );
+
+        fs::remove_file(backup.unwrap()).ok();
+        fs::remove_file(&path).ok();
+    }
+
+    /* ----------------------------------------KDF Parameter Upgrade -------------------------- */
+
+    #[test]
+    fn unlock_transparently_upgrades_weak_kdf_parameters() {
+        use storage::VaultBackend;
+
+        let backend = storage::InMemoryBackend::new();
+        let password = "WeakKdfPassword123!@#";
+        let salt = [7u8; 16];
+
+        let weak_params = crypto::KdfParams {
+            m_cost: 1024,
+            t_cost: 1,
+            p_cost: 1,
+        };
+
+        let weak_key = crypto::MasterKey::from_password_with_params(password, &salt, weak_params)
+            .expect("derive weak key");
+
+        let plain = vault::Vault::<vault::Plain>::new();
+        let encrypted = plain.encrypt(&weak_key).expect("encrypt under weak params");
+        storage::save_vault(&backend, &encrypted).expect("save weak vault");
+
+        let (_, active_key) =
+            storage::load_vault(&backend, password).expect("unlock should succeed and upgrade");
+
+        assert_eq!(active_key.params(), crypto::KdfParams::CURRENT);
+
+        let rewritten = crypto::EncryptedVault::from_bytes(&backend.read_blob().unwrap())
+            .expect("parse rewritten container");
+        assert_eq!(rewritten.kdf_params(), crypto::KdfParams::CURRENT);
+    }
+
+    /* ----------------------------------------Version Migration & Header Authentication -------------------------- */
+
+    #[test]
+    fn future_version_byte_is_rejected_distinctly_from_corruption() {
+        use storage::VaultBackend;
+
+        let backend = storage::InMemoryBackend::new();
+        let password = "FutureVersionPassword123!@#";
+
+        storage::create_vault(&backend, password, &vault::Vault::<vault::Plain>::new())
+            .expect("create vault");
+
+        let mut bytes = backend.read_blob().unwrap();
+        // Header layout: magic(4) + version(2, big-endian) + kdf(1) + aead(1).
+        let future_version = crypto::EncryptedVault::CURRENT_VERSION + 1;
+        bytes[4..6].copy_from_slice(&future_version.to_be_bytes());
+
+        assert!(matches!(
+            crypto::EncryptedVault::from_bytes(&bytes),
+            Err(errors::VaultError::UnsupportedVersion)
+        ));
+    }
+
+    #[test]
+    fn tampered_header_byte_fails_authentication() {
+        use storage::VaultBackend;
+
+        let backend = storage::InMemoryBackend::new();
+        let password = "TamperedHeaderPassword123!@#";
+
+        storage::create_vault(&backend, password, &vault::Vault::<vault::Plain>::new())
+            .expect("create vault");
+
+        let mut bytes = backend.read_blob().unwrap();
+        // Flip a low bit of the version byte: still a recognized (older)
+        // version, so the header parses, but the header is authenticated
+        // as AEAD associated data, so decryption must still fail.
+        bytes[5] ^= 0x01;
+        backend.write_blob(&bytes).unwrap();
+
+        assert!(matches!(
+            storage::load_vault(&backend, password),
+            Err(errors::VaultError::CryptoError)
+        ));
+    }
+}